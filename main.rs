@@ -1,8 +1,63 @@
 use std::io::{self, BufRead, BufReader, Error, Read, Write};
+use std::path::Path;
 
-use clap::Parser;
-use dithord::{OrderedDither, ThresholdMap};
-use image::io::Reader;
+use clap::{Parser, ValueEnum};
+use dithord::output::{self, TiffCompression as OutputTiffCompression};
+use dithord::{OrderedDither, ThresholdMap, ToPackedBitmap};
+use image::{io::Reader, Rgb};
+
+/// Threshold map generation method
+#[derive(ValueEnum, Clone, Debug)]
+enum MapType {
+    /// Deterministic Bayer matrix, sized by `--level`
+    Bayer,
+    /// Seeded blue-noise map generated by void-and-cluster, sized by `--size`
+    BlueNoise,
+}
+
+/// Dithering mode to apply to the input image
+#[derive(ValueEnum, Clone, Debug)]
+enum Mode {
+    /// Threshold luma to black/white
+    Luma,
+    /// Quantize each color channel independently to `--levels` values
+    Rgb,
+    /// Quantize to the nearest color in a `--palette` file
+    Palette,
+}
+
+/// Output container format
+#[derive(ValueEnum, Clone, Debug, PartialEq)]
+enum Format {
+    /// Full-color PNG
+    Png,
+    /// 1-bit-per-pixel grayscale PNG
+    PngPacked,
+    /// TIFF, using `--tiff-compression`
+    Tiff,
+    /// Binary (P4) PBM bitstream
+    Pbm,
+    /// Raw 1-bit-per-pixel bitstream with no header or container
+    Raw,
+}
+
+/// TIFF compression scheme, used when the output format is TIFF
+#[derive(ValueEnum, Clone, Debug)]
+enum TiffCompression {
+    Deflate,
+    Lzw,
+    PackBits,
+}
+
+impl From<TiffCompression> for OutputTiffCompression {
+    fn from(value: TiffCompression) -> Self {
+        match value {
+            TiffCompression::Deflate => OutputTiffCompression::Deflate,
+            TiffCompression::Lzw => OutputTiffCompression::Lzw,
+            TiffCompression::PackBits => OutputTiffCompression::PackBits,
+        }
+    }
+}
 
 /// Bayer ordered dithering utility
 #[derive(Parser, Debug)]
@@ -14,9 +69,80 @@ struct Args {
     /// Output image file path
     pub output: String,
 
-    /// Threshold map level
+    /// Threshold map level, used when `--map-type bayer`
     #[clap(short, long, default_value = "2")]
     pub level: u32,
+
+    /// Threshold map generation method
+    #[clap(long, value_enum, default_value = "bayer")]
+    pub map_type: MapType,
+
+    /// Side length of the generated map, used when `--map-type blue-noise`
+    #[clap(long, default_value = "16")]
+    pub size: usize,
+
+    /// RNG seed for blue-noise map generation, used when `--map-type blue-noise`
+    #[clap(long, default_value = "0")]
+    pub seed: u64,
+
+    /// Dithering mode
+    #[clap(short, long, value_enum, default_value = "luma")]
+    pub mode: Mode,
+
+    /// Number of output levels per channel, used by `--mode rgb`
+    #[clap(long, default_value = "2")]
+    pub levels: u32,
+
+    /// Path to a palette file (one `#RRGGBB` color per line), used by `--mode palette`
+    #[clap(long)]
+    pub palette: Option<String>,
+
+    /// Choose the PNG scanline filter per row to minimize output size
+    #[clap(long)]
+    pub optimize: bool,
+
+    /// Output container format; inferred from the output path's extension if omitted
+    #[clap(long, value_enum)]
+    pub format: Option<Format>,
+
+    /// TIFF compression scheme, used when the output format is TIFF
+    #[clap(long, value_enum, default_value = "deflate")]
+    pub tiff_compression: TiffCompression,
+}
+
+/// Determines the output format from `--format`, falling back to the output path's extension.
+fn resolve_format(args: &Args) -> Format {
+    if let Some(format) = args.format.clone() {
+        return format;
+    }
+    match Path::new(&args.output)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_lowercase)
+        .as_deref()
+    {
+        Some("tif") | Some("tiff") => Format::Tiff,
+        Some("pbm") => Format::Pbm,
+        _ => Format::Png,
+    }
+}
+
+/// Parses a palette file of one `#RRGGBB` (or `RRGGBB`) color per line.
+fn load_palette(path: &str) -> Vec<Rgb<u8>> {
+    let contents = std::fs::read_to_string(path).expect("Failed to read palette file.");
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let hex = line.trim_start_matches('#');
+            let channel = |i: usize| {
+                u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)
+                    .expect("Invalid color in palette file.")
+            };
+            Rgb([channel(0), channel(1), channel(2)])
+        })
+        .collect()
 }
 
 pub fn main() {
@@ -45,29 +171,70 @@ pub fn main() {
     let mut image = reader.decode().expect("Failed to decode image.");
 
     // Generate threshold map
-    let threshold_map = ThresholdMap::level(args.level);
+    let threshold_map = match args.map_type {
+        MapType::Bayer => ThresholdMap::level(args.level),
+        MapType::BlueNoise => ThresholdMap::blue_noise(args.size, args.seed),
+    };
 
     // Apply dithering
-    image = image.ordered_dither(&threshold_map);
+    image = match args.mode {
+        Mode::Luma => image.ordered_dither(&threshold_map),
+        Mode::Rgb => image.ordered_dither_levels(&threshold_map, args.levels),
+        Mode::Palette => {
+            let palette = load_palette(
+                args.palette
+                    .as_deref()
+                    .expect("--palette is required for --mode palette"),
+            );
+            image.ordered_dither_palette(&threshold_map, &palette)
+        }
+    };
 
     // Save output image
-    let image = image.to_rgba8();
+    let format = resolve_format(&args);
+    let mut out_buffer = Vec::<u8>::new();
+
+    match format {
+        Format::Png => {
+            let rgba = image.to_rgba8();
+            if args.optimize {
+                output::write_optimized_png(&rgba, &mut out_buffer);
+            } else {
+                rgba.write_to(
+                    &mut io::Cursor::new(&mut out_buffer),
+                    image::ImageFormat::Png,
+                )
+                .expect("Failed to encode output image.");
+            }
+        }
+        Format::Tiff => {
+            let rgba = image.to_rgba8();
+            output::write_tiff(
+                &rgba,
+                io::Cursor::new(&mut out_buffer),
+                args.tiff_compression.clone().into(),
+            );
+        }
+        Format::Pbm => {
+            let luma = image.to_luma8();
+            output::write_pbm(&luma, &mut out_buffer);
+        }
+        Format::PngPacked => {
+            let packed = image.to_packed_bitmap();
+            output::write_packed_png(&packed, &mut out_buffer);
+        }
+        Format::Raw => {
+            let packed = image.to_packed_bitmap();
+            output::write_raw_bitmap(&packed, &mut out_buffer);
+        }
+    }
 
     if args.output == "-" {
-        let mut out_buffer = Vec::<u8>::new();
-        image
-            .write_to(
-                &mut io::Cursor::new(&mut out_buffer),
-                image::ImageFormat::Png,
-            )
-            .expect("Failed to encode output image.");
         let mut out = io::stdout();
         out.write_all(&out_buffer)
             .expect("Failed to write image bytes to stdout");
         out.flush().expect("Failed to flush stdout");
     } else {
-        image
-            .save(&args.output)
-            .expect("Failed to save output image");
+        std::fs::write(&args.output, &out_buffer).expect("Failed to save output image");
     }
 }
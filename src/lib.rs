@@ -0,0 +1,8 @@
+pub mod ordered_dither;
+pub mod output;
+pub mod packed;
+pub mod threshold_map;
+
+pub use ordered_dither::OrderedDither;
+pub use packed::{PackedBitmap, ToPackedBitmap};
+pub use threshold_map::ThresholdMap;
@@ -1,6 +1,6 @@
 use std::time::Instant;
 
-use image::DynamicImage;
+use image::{DynamicImage, Rgb};
 
 use crate::ThresholdMap;
 
@@ -8,21 +8,78 @@ use crate::ThresholdMap;
 pub trait OrderedDither {
     /// Performs an ordered dither on a copy of self, returning the result.
     ///
+    /// Thresholds image luma against the map, producing a 2-level (black/white) result.
+    ///
     /// # Arguments
     ///
     /// * `threshold_map` - Threshold map to use for dithering
     ///
-    fn ordered_dither(&self, threshold_map: ThresholdMap) -> Self;
+    fn ordered_dither(&self, threshold_map: &ThresholdMap) -> Self;
+
+    /// Performs an ordered dither on a copy of self, quantizing each color channel to `levels`
+    /// evenly spaced values instead of simple thresholding.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold_map` - Threshold map to use for dithering
+    /// * `levels` - Number of output levels per channel
+    ///
+    fn ordered_dither_levels(&self, threshold_map: &ThresholdMap, levels: u32) -> Self;
+
+    /// Performs an ordered dither on a copy of self, quantizing each pixel to the nearest color
+    /// in `palette` after perturbing it by the threshold map.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold_map` - Threshold map to use for dithering
+    /// * `palette` - Output colors to quantize to
+    ///
+    fn ordered_dither_palette(&self, threshold_map: &ThresholdMap, palette: &[Rgb<u8>]) -> Self;
 }
 
 impl OrderedDither for DynamicImage {
-    fn ordered_dither(&self, threshold_map: ThresholdMap) -> Self {
+    fn ordered_dither(&self, threshold_map: &ThresholdMap) -> Self {
         let width = self.width() as usize;
         // Convert image to luma float image for convenient comparison
         let mut copy = self.to_luma32f().clone();
         let start = Instant::now();
         copy.pixels_mut().enumerate().for_each(|(i, pixel)| {
-            pixel.0[0] = test_pixel(&threshold_map, pixel.0[0], i % width, i / width) as u32 as f32;
+            pixel.0[0] = test_pixel(threshold_map, pixel.0[0], i % width, i / width) as u32 as f32;
+        });
+        println!("Dithered in {}", start.elapsed().as_millis());
+        copy.into()
+    }
+
+    fn ordered_dither_levels(&self, threshold_map: &ThresholdMap, levels: u32) -> Self {
+        let width = self.width() as usize;
+        let mut copy = self.to_rgba32f();
+        let start = Instant::now();
+        copy.pixels_mut().enumerate().for_each(|(i, pixel)| {
+            let (x, y) = (i % width, i / width);
+            for channel in pixel.0.iter_mut().take(3) {
+                *channel = quantize_channel(threshold_map, *channel, levels, x, y);
+            }
+        });
+        println!("Dithered in {}", start.elapsed().as_millis());
+        copy.into()
+    }
+
+    fn ordered_dither_palette(&self, threshold_map: &ThresholdMap, palette: &[Rgb<u8>]) -> Self {
+        let width = self.width() as usize;
+        let mut copy = self.to_rgba32f();
+        let start = Instant::now();
+        copy.pixels_mut().enumerate().for_each(|(i, pixel)| {
+            let (x, y) = (i % width, i / width);
+            let offset = threshold_map.sample(x, y) - 0.5;
+            let nearest = nearest_palette_color(
+                palette,
+                pixel.0[0] + offset,
+                pixel.0[1] + offset,
+                pixel.0[2] + offset,
+            );
+            pixel.0[0] = nearest.0[0] as f32 / 255.0;
+            pixel.0[1] = nearest.0[1] as f32 / 255.0;
+            pixel.0[2] = nearest.0[2] as f32 / 255.0;
         });
         println!("Dithered in {}", start.elapsed().as_millis());
         copy.into()
@@ -30,10 +87,39 @@ impl OrderedDither for DynamicImage {
 }
 
 /// Tests pixel luma against threshold map
-fn test_pixel(map: &ThresholdMap, luma: f32,  x: usize, y: usize) -> bool {
+fn test_pixel(map: &ThresholdMap, luma: f32, x: usize, y: usize) -> bool {
     luma > map.sample(x, y)
 }
 
+/// Quantizes a normalized channel value to the nearest of `levels` evenly spaced values, after
+/// perturbing it by the threshold map sample at the given coordinates.
+fn quantize_channel(map: &ThresholdMap, value: f32, levels: u32, x: usize, y: usize) -> f32 {
+    let steps = (levels.max(2) - 1) as f32;
+    let spread = 1.0 / steps;
+    let perturbed = value + spread * (map.sample(x, y) - 0.5);
+    (perturbed * steps).round().clamp(0.0, steps) / steps
+}
+
+/// Finds the color in `palette` closest to the given normalized RGB values by Euclidean distance.
+fn nearest_palette_color(palette: &[Rgb<u8>], r: f32, g: f32, b: f32) -> Rgb<u8> {
+    *palette
+        .iter()
+        .min_by(|a, b_color| {
+            distance_squared(a, r, g, b)
+                .partial_cmp(&distance_squared(b_color, r, g, b))
+                .unwrap()
+        })
+        .expect("palette must not be empty")
+}
+
+/// Squared Euclidean distance between a palette color and normalized RGB values.
+fn distance_squared(color: &Rgb<u8>, r: f32, g: f32, b: f32) -> f32 {
+    let dr = color.0[0] as f32 / 255.0 - r;
+    let dg = color.0[1] as f32 / 255.0 - g;
+    let db = color.0[2] as f32 / 255.0 - b;
+    dr * dr + dg * dg + db * db
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -48,4 +134,32 @@ mod test {
         let map = ThresholdMap::level(3);
         assert_eq!(test_pixel(&map, luma, x, y), expected)
     }
+
+    #[rstest]
+    #[case(0.5, 3, 0, 0, 0.5)]
+    #[case(0.0, 3, 1, 1, 0.0)]
+    #[case(1.0, 3, 1, 1, 1.0)]
+    fn quantize_channel_test(
+        #[case] value: f32,
+        #[case] levels: u32,
+        #[case] x: usize,
+        #[case] y: usize,
+        #[case] expected: f32,
+    ) {
+        let map = ThresholdMap::level(3);
+        assert_eq!(quantize_channel(&map, value, levels, x, y), expected)
+    }
+
+    #[rstest]
+    fn nearest_palette_color_test() {
+        let palette = vec![Rgb([0, 0, 0]), Rgb([255, 255, 255]), Rgb([255, 0, 0])];
+        assert_eq!(
+            nearest_palette_color(&palette, 0.9, 0.05, 0.05),
+            Rgb([255, 0, 0])
+        );
+        assert_eq!(
+            nearest_palette_color(&palette, 0.05, 0.05, 0.05),
+            Rgb([0, 0, 0])
+        );
+    }
 }
@@ -0,0 +1,100 @@
+use std::io::{self, Write};
+
+use image::codecs::pnm::{PnmEncoder, PnmSubtype, SampleEncoding};
+use image::{ExtendedColorType, GrayImage, ImageEncoder, RgbaImage};
+use tiff::encoder::{colortype, compression, TiffEncoder};
+
+use crate::PackedBitmap;
+
+/// TIFF compression scheme used by [`write_tiff`].
+#[derive(Debug, Clone, Copy)]
+pub enum TiffCompression {
+    Deflate,
+    Lzw,
+    PackBits,
+}
+
+/// Encodes `image` as PNG, choosing the scanline filter per row that minimizes the sum of
+/// absolute values of the filtered bytes. This shrinks the high-frequency patterns ordered
+/// dithering produces substantially compared to a single fixed filter.
+pub fn write_optimized_png<W: Write>(image: &RgbaImage, writer: W) {
+    let mut encoder = png::Encoder::new(writer, image.width(), image.height());
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_adaptive_filter(png::AdaptiveFilterType::Adaptive);
+    let mut writer = encoder
+        .write_header()
+        .expect("Failed to write PNG header.");
+    writer
+        .write_image_data(image.as_raw())
+        .expect("Failed to encode output image.");
+}
+
+/// Encodes `image` as TIFF, using `compression` to shrink the effectively-bilevel data dithering
+/// produces.
+pub fn write_tiff<W: Write + io::Seek>(
+    image: &RgbaImage,
+    writer: W,
+    compression: TiffCompression,
+) {
+    let mut encoder = TiffEncoder::new(writer).expect("Failed to create TIFF encoder.");
+    let (width, height) = image.dimensions();
+    let data = image.as_raw();
+    let result = match compression {
+        TiffCompression::Deflate => encoder.write_image_with_compression::<colortype::RGBA8, _>(
+            width,
+            height,
+            compression::Deflate::default(),
+            data,
+        ),
+        TiffCompression::Lzw => encoder.write_image_with_compression::<colortype::RGBA8, _>(
+            width,
+            height,
+            compression::Lzw,
+            data,
+        ),
+        TiffCompression::PackBits => encoder
+            .write_image_with_compression::<colortype::RGBA8, _>(
+                width,
+                height,
+                compression::Packbits,
+                data,
+            ),
+    };
+    result.expect("Failed to encode output image.");
+}
+
+/// Encodes `image` as a binary (P4) PBM bitstream, the natural format for bilevel dithered
+/// output.
+pub fn write_pbm<W: Write>(image: &GrayImage, writer: W) {
+    PnmEncoder::new(writer)
+        .with_subtype(PnmSubtype::Bitmap(SampleEncoding::Binary))
+        .write_image(
+            image.as_raw(),
+            image.width(),
+            image.height(),
+            ExtendedColorType::L8,
+        )
+        .expect("Failed to encode output image.");
+}
+
+/// Encodes `packed` as a 1-bit-per-pixel grayscale PNG, avoiding the 32-bits-per-pixel bloat of
+/// expanding a bilevel result back out to RGBA before saving.
+pub fn write_packed_png<W: Write>(packed: &PackedBitmap, writer: W) {
+    let mut encoder = png::Encoder::new(writer, packed.width, packed.height);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::One);
+    let mut writer = encoder
+        .write_header()
+        .expect("Failed to write PNG header.");
+    writer
+        .write_image_data(&packed.data)
+        .expect("Failed to encode output image.");
+}
+
+/// Writes `packed` as a raw 1-bit-per-pixel bitstream, with no header or container.
+pub fn write_raw_bitmap<W: Write>(packed: &PackedBitmap, mut writer: W) {
+    writer
+        .write_all(&packed.data)
+        .expect("Failed to write output image.");
+}
@@ -0,0 +1,67 @@
+use image::DynamicImage;
+
+/// A 1-bit-per-pixel bitmap, packed MSB-first with each row padded to a byte boundary.
+#[derive(Debug, PartialEq)]
+pub struct PackedBitmap {
+    pub data: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl PackedBitmap {
+    /// Number of packed bytes per row.
+    pub fn row_bytes(&self) -> usize {
+        (self.width as usize).div_ceil(8)
+    }
+}
+
+/// Trait which enables packing an image down to a compact 1-bit-per-pixel bitmap
+pub trait ToPackedBitmap {
+    /// Packs self into a [`PackedBitmap`], thresholding luma at the midpoint of its range.
+    fn to_packed_bitmap(&self) -> PackedBitmap;
+}
+
+impl ToPackedBitmap for DynamicImage {
+    fn to_packed_bitmap(&self) -> PackedBitmap {
+        let luma = self.to_luma8();
+        let width = luma.width();
+        let height = luma.height();
+        let row_bytes = (width as usize).div_ceil(8);
+        let mut data = vec![0u8; row_bytes * height as usize];
+        for (x, y, pixel) in luma.enumerate_pixels() {
+            if pixel.0[0] >= 128 {
+                let byte = y as usize * row_bytes + x as usize / 8;
+                let bit = 7 - (x as usize % 8);
+                data[byte] |= 1 << bit;
+            }
+        }
+        PackedBitmap {
+            data,
+            width,
+            height,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use image::{GrayImage, Luma};
+
+    #[test]
+    fn packs_msb_first_with_row_padding() {
+        let mut image = GrayImage::new(9, 2);
+        image.put_pixel(0, 0, Luma([255]));
+        image.put_pixel(8, 0, Luma([255]));
+        image.put_pixel(1, 1, Luma([255]));
+        let packed = DynamicImage::ImageLuma8(image).to_packed_bitmap();
+
+        assert_eq!(packed.width, 9);
+        assert_eq!(packed.height, 2);
+        assert_eq!(packed.row_bytes(), 2);
+        assert_eq!(
+            packed.data,
+            vec![0b1000_0000, 0b1000_0000, 0b0100_0000, 0b0000_0000]
+        );
+    }
+}
@@ -1,3 +1,10 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Standard deviation of the Gaussian filter used to judge cluster tightness and void size
+/// during blue-noise generation.
+const BLUE_NOISE_SIGMA: f32 = 1.5;
+
 /// Represents the threshold map Bayer matrix used for dithering
 #[derive(Debug, PartialEq)]
 pub struct ThresholdMap(pub(crate) Vec<Vec<f32>>);
@@ -43,6 +50,59 @@ impl ThresholdMap {
         Self(matrix)
     }
 
+    /// Generates a blue-noise threshold map of the given `size` using the void-and-cluster
+    /// method, seeded by `seed` for reproducible output.
+    ///
+    /// Unlike [`ThresholdMap::level`]'s Bayer matrix, blue-noise maps have no periodic structure,
+    /// avoiding the visible cross-hatch artifacts ordered dithering with a Bayer matrix produces.
+    /// This is an implementation of the void-and-cluster algorithm described by Ulichney: starting
+    /// from a small seeded-random initial pattern, it is relaxed into a stable binary pattern by
+    /// repeatedly swapping its tightest cluster for its largest void (judged by a toroidal
+    /// Gaussian-weighted neighbor sum), then ranked outward from that pattern in two phases -
+    /// removing clusters in descending rank order, then filling voids in ascending rank order.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - Side length of the square map to generate
+    /// * `seed` - Seed for the RNG used to place the initial pattern
+    ///
+    pub fn blue_noise(size: usize, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let kernel = gaussian_kernel(BLUE_NOISE_SIGMA);
+        let initial_pattern = stable_initial_pattern(size, &kernel, &mut rng);
+        let ones = initial_pattern.iter().filter(|&&set| set).count();
+
+        let mut ranks = vec![0i64; size * size];
+
+        // Phase 1: remove the initial pattern's clusters one at a time, ranking them in
+        // descending order, down to the single tightest cluster at rank 0.
+        let mut pattern = initial_pattern.clone();
+        for rank in (0..ones as i64).rev() {
+            let index = tightest_cluster(&pattern, &kernel, size);
+            pattern[index] = false;
+            ranks[index] = rank;
+        }
+
+        // Phase 2: starting back from the initial pattern, fill voids one at a time, ranking
+        // them in ascending order.
+        let mut pattern = initial_pattern;
+        for rank in ones..(size * size) {
+            let index = largest_void(&pattern, &kernel, size);
+            pattern[index] = true;
+            ranks[index] = rank as i64;
+        }
+
+        let level_count = (size * size) as f32;
+        let mut matrix = vec![vec![0.0; size]; size];
+        for (y, row) in matrix.iter_mut().enumerate() {
+            for (x, cell) in row.iter_mut().enumerate() {
+                *cell = ranks[y * size + x] as f32 / level_count;
+            }
+        }
+
+        Self(matrix)
+    }
+
     /// Sample this threshold at the given x and y coordinates, wrapping around as necessary
     ///
     /// # Arguments
@@ -56,6 +116,88 @@ impl ThresholdMap {
     }
 }
 
+/// Builds a truncated Gaussian kernel with the given standard deviation, wide enough (3 standard
+/// deviations in each direction) to approximate an infinite toroidal filter.
+fn gaussian_kernel(sigma: f32) -> Vec<Vec<f32>> {
+    let radius = (3.0 * sigma).ceil() as i32;
+    let size = (2 * radius + 1) as usize;
+    let mut kernel = vec![vec![0.0; size]; size];
+    for (dy, row) in kernel.iter_mut().enumerate() {
+        for (dx, weight) in row.iter_mut().enumerate() {
+            let x = dx as f32 - radius as f32;
+            let y = dy as f32 - radius as f32;
+            *weight = (-(x * x + y * y) / (2.0 * sigma * sigma)).exp();
+        }
+    }
+    kernel
+}
+
+/// Gaussian-weighted, toroidally-wrapped neighbor sum of the set pixels around `(x, y)`.
+fn neighbor_sum(pattern: &[bool], kernel: &[Vec<f32>], size: usize, x: usize, y: usize) -> f32 {
+    let radius = (kernel.len() / 2) as i32;
+    let mut sum = 0.0;
+    for (dy, row) in kernel.iter().enumerate() {
+        for (dx, &weight) in row.iter().enumerate() {
+            let ox = (x as i32 + dx as i32 - radius).rem_euclid(size as i32) as usize;
+            let oy = (y as i32 + dy as i32 - radius).rem_euclid(size as i32) as usize;
+            if pattern[oy * size + ox] {
+                sum += weight;
+            }
+        }
+    }
+    sum
+}
+
+/// Index of the set pixel with the largest Gaussian-weighted neighbor sum - the tightest cluster.
+fn tightest_cluster(pattern: &[bool], kernel: &[Vec<f32>], size: usize) -> usize {
+    (0..pattern.len())
+        .filter(|&i| pattern[i])
+        .map(|i| (i, neighbor_sum(pattern, kernel, size, i % size, i / size)))
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+        .map(|(i, _)| i)
+        .expect("pattern must contain at least one set pixel")
+}
+
+/// Index of the unset pixel with the smallest Gaussian-weighted neighbor sum - the largest void.
+fn largest_void(pattern: &[bool], kernel: &[Vec<f32>], size: usize) -> usize {
+    (0..pattern.len())
+        .filter(|&i| !pattern[i])
+        .map(|i| (i, neighbor_sum(pattern, kernel, size, i % size, i / size)))
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+        .map(|(i, _)| i)
+        .expect("pattern must contain at least one unset pixel")
+}
+
+/// Generates a small random initial binary pattern and relaxes it into a stable one: a pattern
+/// whose tightest cluster is also its largest void once removed, i.e. swapping the two wouldn't
+/// change which pixel is which.
+fn stable_initial_pattern(size: usize, kernel: &[Vec<f32>], rng: &mut impl Rng) -> Vec<bool> {
+    let initial_count = (size * size / 10).max(1);
+    let mut pattern = vec![false; size * size];
+    let mut placed = 0;
+    while placed < initial_count {
+        let index = rng.gen_range(0..pattern.len());
+        if !pattern[index] {
+            pattern[index] = true;
+            placed += 1;
+        }
+    }
+
+    loop {
+        let cluster = tightest_cluster(&pattern, kernel, size);
+        pattern[cluster] = false;
+        let void = largest_void(&pattern, kernel, size);
+        pattern[cluster] = true;
+        if void == cluster {
+            break;
+        }
+        pattern[cluster] = false;
+        pattern[void] = true;
+    }
+
+    pattern
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -106,4 +248,26 @@ mod test {
         ].map(|row: [f32;8] | row.map(|cell| cell / 64.0).into()).into());
         assert_eq!(map.sample(x, y), expected)
     }
+
+    #[rstest]
+    fn blue_noise_is_deterministic_per_seed() {
+        let a = ThresholdMap::blue_noise(8, 42);
+        let b = ThresholdMap::blue_noise(8, 42);
+        let c = ThresholdMap::blue_noise(8, 7);
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[rstest]
+    fn blue_noise_covers_every_rank_exactly_once() {
+        let map = ThresholdMap::blue_noise(8, 42);
+        let mut ranks: Vec<i64> = map
+            .0
+            .iter()
+            .flatten()
+            .map(|&cell| (cell * 64.0).round() as i64)
+            .collect();
+        ranks.sort_unstable();
+        assert_eq!(ranks, (0..64).collect::<Vec<_>>());
+    }
 }